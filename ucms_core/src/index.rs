@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+/// How a multi-term query combines its per-term postings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// A document must contain every query term.
+    And,
+    /// A document must contain at least one query term.
+    Or,
+}
+
+/// An inverted index over document content, kept in sync with a document
+/// set's `add`/`update`/`delete` calls. Supports ranked full-text search
+/// via TF scoring weighted by inverse document frequency.
+#[derive(Debug, Default)]
+pub struct Index {
+    postings: HashMap<String, HashSet<usize>>,
+    term_frequencies: HashMap<usize, HashMap<String, usize>>,
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+fn term_frequencies(content: &str) -> HashMap<String, usize> {
+    let mut frequencies = HashMap::new();
+    for term in tokenize(content) {
+        *frequencies.entry(term).or_insert(0) += 1;
+    }
+    frequencies
+}
+
+impl Index {
+    pub fn new() -> Index {
+        Index::default()
+    }
+
+    /// Tokenizes `content` and adds `id` to the postings for each term it
+    /// contains.
+    pub fn add<T: AsRef<str>>(&mut self, id: usize, content: &T) {
+        let frequencies = term_frequencies(content.as_ref());
+        for term in frequencies.keys() {
+            self.postings.entry(term.clone()).or_default().insert(id);
+        }
+        self.term_frequencies.insert(id, frequencies);
+    }
+
+    /// Re-tokenizes `content` and drops `id` from postings of terms it no
+    /// longer contains.
+    pub fn update<T: AsRef<str>>(&mut self, id: usize, content: &T) {
+        self.delete(id);
+        self.add(id, content);
+    }
+
+    /// Removes `id` from every posting list it appears in.
+    pub fn delete(&mut self, id: usize) {
+        let Some(frequencies) = self.term_frequencies.remove(&id) else {
+            return;
+        };
+        for term in frequencies.keys() {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+    }
+
+    /// Tokenizes `query` the same way as indexed content, combines the
+    /// per-term posting sets according to `mode`, and ranks matches by
+    /// descending TF-IDF score.
+    pub fn search(&self, query: &str, mode: SearchMode) -> Vec<usize> {
+        let terms = tokenize(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let postings_for = |term: &str| self.postings.get(term).cloned().unwrap_or_default();
+        let candidates = match mode {
+            SearchMode::And => terms
+                .iter()
+                .map(|term| postings_for(term))
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap_or_default(),
+            SearchMode::Or => terms.iter().flat_map(|term| postings_for(term)).collect(),
+        };
+
+        let mut scored: Vec<(usize, f64)> = candidates
+            .into_iter()
+            .map(|id| (id, self.score(id, &terms)))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn score(&self, id: usize, terms: &[String]) -> f64 {
+        let total_documents = self.term_frequencies.len() as f64;
+        let Some(frequencies) = self.term_frequencies.get(&id) else {
+            return 0.0;
+        };
+        terms
+            .iter()
+            .map(|term| {
+                let tf = *frequencies.get(term).unwrap_or(&0) as f64;
+                if tf == 0.0 {
+                    return 0.0;
+                }
+                let df = self.postings.get(term).map_or(0, HashSet::len) as f64;
+                tf * (total_documents / df).ln()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_ranks_by_tf_idf() {
+        let mut index = Index::new();
+        index.add(1, &"the quick brown fox");
+        index.add(2, &"the quick quick dog");
+        index.add(3, &"completely unrelated text");
+
+        assert_eq!(index.search("quick", SearchMode::Or), vec![2, 1]);
+        assert_eq!(index.search("quick fox", SearchMode::And), vec![1]);
+        assert_eq!(index.search("quick fox", SearchMode::Or), vec![1, 2]);
+        assert_eq!(index.search("nonexistent", SearchMode::Or), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn update_and_delete_keep_postings_in_sync() {
+        let mut index = Index::new();
+        index.add(1, &"hello world");
+        index.update(1, &"goodbye");
+
+        assert_eq!(index.search("hello", SearchMode::Or), Vec::<usize>::new());
+        assert_eq!(index.search("goodbye", SearchMode::Or), vec![1]);
+
+        index.delete(1);
+        assert_eq!(index.search("goodbye", SearchMode::Or), Vec::<usize>::new());
+    }
+}
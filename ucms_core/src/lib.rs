@@ -1,12 +1,30 @@
 use std::fmt;
 
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+mod index;
+mod map_storage;
+mod persistent;
+
+pub use index::{Index, SearchMode};
+pub use map_storage::MapStorage;
+pub use persistent::PersistentStorage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revision<T> {
+    content: T,
+    modified_at: u64,
+    version: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Document<T> {
     id: usize,
     content: T,
     created_at: u64,
     modified_at: Option<u64>,
     version: u32,
+    history: Vec<Revision<T>>,
 }
 
 impl<T: fmt::Display> fmt::Display for Document<T> {
@@ -27,17 +45,58 @@ impl<T> Document<T> {
             created_at,
             modified_at: None,
             version: 0,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn content(&self) -> &T {
+        &self.content
+    }
+
+    /// Returns a new revision of this document, archiving the current
+    /// content as an immutable snapshot so it stays reachable through
+    /// [`Document::history`].
+    pub fn update(&self, content: T, modified_at: u64) -> Document<T>
+    where
+        T: Clone,
+    {
+        let mut history = self.history.clone();
+        history.push(Revision {
+            content: self.content.clone(),
+            modified_at: self.modified_at.unwrap_or(self.created_at),
+            version: self.version,
+        });
+        Document {
+            id: self.id,
+            content,
+            created_at: self.created_at,
+            modified_at: Some(modified_at),
+            version: self.version + 1,
+            history,
         }
     }
 
-    pub fn update(&self, content: T, modified_at: u64)->Document<T> {
-       Document{
-              id: self.id,
-              content,
-              created_at: self.created_at,
-              modified_at: Some(modified_at),
-              version: self.version + 1,
-       }
+    /// Returns the content as it stood at `version`, whether that's the
+    /// current revision or one retained in history.
+    pub fn at_version(&self, version: u32) -> Option<&T> {
+        if version == self.version {
+            Some(&self.content)
+        } else {
+            self.history
+                .iter()
+                .find(|revision| revision.version == version)
+                .map(|revision| &revision.content)
+        }
+    }
+
+    /// Iterates over every past revision's content, oldest first, not
+    /// including the current one.
+    pub fn history(&self) -> impl Iterator<Item = &T> {
+        self.history.iter().map(|revision| &revision.content)
     }
 }
 
@@ -46,9 +105,17 @@ pub trait Storage<T> {
     fn get(&self, id: usize) -> Option<&Document<T>>;
     fn update(&mut self, id: usize, content: T, modified_at: u64) -> bool;
     fn delete(&mut self, id: usize) -> bool;
+    /// Looks up the content of `id` as it stood at `version`, whether
+    /// that's the current revision or one retained in history.
+    fn get_version(&self, id: usize, version: u32) -> Option<&T>;
+    /// Iterates over `id`'s past revisions, oldest first, not including
+    /// the current one. Yields nothing if `id` doesn't exist.
+    fn history<'a>(&'a self, id: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
 }
 
-impl<T> Storage<T> for Vec<Document<T>> {
+impl<T: Clone> Storage<T> for Vec<Document<T>> {
     fn add(&mut self, document: Document<T>) {
         self.push(document);
     }
@@ -74,6 +141,17 @@ impl<T> Storage<T> for Vec<Document<T>> {
             false
         }
     }
+
+    fn get_version(&self, id: usize, version: u32) -> Option<&T> {
+        self.get(id)?.at_version(version)
+    }
+
+    fn history<'a>(&'a self, id: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        self.get(id).into_iter().flat_map(Document::history)
+    }
 }
 
 
@@ -112,4 +190,20 @@ mod tests {
         assert_eq!(storage.delete(1), true);
         assert_eq!(storage.delete(1), false);
     }
+
+    #[test]
+    fn version_history() {
+        let mut storage: Vec<Document<&str>> = Vec::new();
+        storage.add(Document::new(1, "v0", 0));
+        storage.update(1, "v1", 1);
+        storage.update(1, "v2", 2);
+
+        assert_eq!(storage.history(1).collect::<Vec<_>>(), vec![&"v0", &"v1"]);
+        assert_eq!(storage.get_version(1, 0), Some(&"v0"));
+        assert_eq!(storage.get_version(1, 1), Some(&"v1"));
+        assert_eq!(storage.get_version(1, 2), Some(&"v2"));
+        assert_eq!(storage.get_version(1, 3), None);
+        assert_eq!(storage.get_version(404, 0), None);
+        assert_eq!(storage.history(404).next(), None);
+    }
 }
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::{Document, Storage};
+
+/// A [`Storage`] backend keyed by document id, giving O(1) `get`/`update`/
+/// `delete` instead of the `Vec` backend's O(n) linear scans. Prefer
+/// [`MapStorage`] once a collection grows large enough that scan cost
+/// matters; for small collections the `Vec` impl is simpler and its
+/// scans are cheap in absolute terms, so there's no need to switch.
+pub struct MapStorage<T> {
+    documents: HashMap<usize, Document<T>>,
+    next_id: usize,
+}
+
+impl<T> MapStorage<T> {
+    pub fn new() -> MapStorage<T> {
+        MapStorage {
+            documents: HashMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<T: Clone> MapStorage<T> {
+    /// Allocates the next free id, stores `content` under it, and returns
+    /// the id that was assigned, so callers don't have to hand-pick ids.
+    pub fn insert(&mut self, content: T, created_at: u64) -> usize {
+        let id = self.next_id;
+        self.add(Document::new(id, content, created_at));
+        id
+    }
+}
+
+impl<T> Default for MapStorage<T> {
+    fn default() -> Self {
+        MapStorage::new()
+    }
+}
+
+impl<T: Clone> Storage<T> for MapStorage<T> {
+    fn add(&mut self, document: Document<T>) {
+        self.next_id = self.next_id.max(document.id() + 1);
+        self.documents.insert(document.id(), document);
+    }
+
+    fn get(&self, id: usize) -> Option<&Document<T>> {
+        self.documents.get(&id)
+    }
+
+    fn update(&mut self, id: usize, content: T, modified_at: u64) -> bool {
+        let Some(updated) = self.documents.get(&id).map(|doc| doc.update(content, modified_at))
+        else {
+            return false;
+        };
+        self.documents.insert(id, updated);
+        true
+    }
+
+    fn delete(&mut self, id: usize) -> bool {
+        self.documents.remove(&id).is_some()
+    }
+
+    fn get_version(&self, id: usize, version: u32) -> Option<&T> {
+        self.documents.get(&id)?.at_version(version)
+    }
+
+    fn history<'a>(&'a self, id: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        self.documents.get(&id).into_iter().flat_map(Document::history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage() {
+        let mut storage: MapStorage<&str> = MapStorage::new();
+        let first = storage.insert("Hello, world!", 0);
+        let second = storage.insert("Hello, Rust!", 1);
+
+        assert_eq!(storage.get(first).unwrap().content(), &"Hello, world!");
+        assert_eq!(storage.get(second).unwrap().content(), &"Hello, Rust!");
+
+        assert_eq!(storage.update(first, "Hello, Rust!", 2), true);
+        assert_eq!(storage.get(first).unwrap().content(), &"Hello, Rust!");
+
+        assert_eq!(storage.delete(first), true);
+        assert_eq!(storage.delete(first), false);
+    }
+
+    #[test]
+    fn insert_never_reuses_an_id() {
+        let mut storage: MapStorage<&str> = MapStorage::new();
+        let first = storage.insert("a", 0);
+        storage.delete(first);
+        let second = storage.insert("b", 1);
+
+        assert_ne!(first, second);
+    }
+}
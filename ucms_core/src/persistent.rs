@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Document, Storage};
+
+/// A [`Storage`] backend that mirrors its document set to a JSON file on
+/// disk, flushing after every mutation so state survives process restarts.
+pub struct PersistentStorage<T> {
+    documents: Vec<Document<T>>,
+    path: PathBuf,
+}
+
+impl<T> PersistentStorage<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Loads the document set from `path`, or starts empty if the file does
+    /// not exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<PersistentStorage<T>> {
+        let path = path.as_ref().to_path_buf();
+        let documents = match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(PersistentStorage { documents, path })
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let raw = serde_json::to_string(&self.documents)?;
+        fs::write(&self.path, raw)
+    }
+
+    /// Opens `path` as a local cache, optionally reconciling it against a
+    /// remote document set.
+    ///
+    /// Offline, this just loads the cache and never calls `remote` —
+    /// useful for working disconnected. Online, it additionally calls
+    /// `remote` for the authoritative document set, persists it over the
+    /// cache, and reports which ids newly appeared and which are now gone
+    /// so a consumer can react to the sync.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        online: bool,
+        remote: impl FnOnce() -> io::Result<Vec<Document<T>>>,
+    ) -> io::Result<(PersistentStorage<T>, Vec<usize>, Vec<usize>)> {
+        let cached = PersistentStorage::load(&path)?;
+        if !online {
+            return Ok((cached, Vec::new(), Vec::new()));
+        }
+
+        let remote_documents = remote()?;
+        let cached_ids: HashSet<usize> = cached.documents.iter().map(Document::id).collect();
+        let remote_ids: HashSet<usize> = remote_documents.iter().map(Document::id).collect();
+
+        let mut new_ids: Vec<usize> = remote_ids.difference(&cached_ids).copied().collect();
+        let mut gone_ids: Vec<usize> = cached_ids.difference(&remote_ids).copied().collect();
+        new_ids.sort_unstable();
+        gone_ids.sort_unstable();
+
+        let reconciled = PersistentStorage {
+            documents: remote_documents,
+            path: path.as_ref().to_path_buf(),
+        };
+        reconciled.flush()?;
+        Ok((reconciled, new_ids, gone_ids))
+    }
+}
+
+impl<T> Storage<T> for PersistentStorage<T>
+where
+    T: Clone + Serialize + DeserializeOwned,
+{
+    fn add(&mut self, document: Document<T>) {
+        self.documents.add(document);
+        self.flush().expect("failed to flush storage to disk");
+    }
+
+    fn get(&self, id: usize) -> Option<&Document<T>> {
+        self.documents.get(id)
+    }
+
+    fn update(&mut self, id: usize, content: T, modified_at: u64) -> bool {
+        let updated = self.documents.update(id, content, modified_at);
+        if updated {
+            self.flush().expect("failed to flush storage to disk");
+        }
+        updated
+    }
+
+    fn delete(&mut self, id: usize) -> bool {
+        let deleted = self.documents.delete(id);
+        if deleted {
+            self.flush().expect("failed to flush storage to disk");
+        }
+        deleted
+    }
+
+    fn get_version(&self, id: usize, version: u32) -> Option<&T> {
+        self.documents.get_version(id, version)
+    }
+
+    fn history<'a>(&'a self, id: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        self.documents.history(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_path(name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("ucms_persistent_{name}_{unique}.json"))
+    }
+
+    #[test]
+    fn load_rehydrates_a_previously_flushed_store() {
+        let path = temp_path("load");
+        let _ = fs::remove_file(&path);
+
+        let mut storage: PersistentStorage<String> = PersistentStorage::load(&path).unwrap();
+        storage.add(Document::new(1, "Hello, world!".to_string(), 0));
+
+        let reloaded: PersistentStorage<String> = PersistentStorage::load(&path).unwrap();
+        assert_eq!(reloaded.get(1).unwrap().content(), "Hello, world!");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn offline_open_never_touches_remote() {
+        let path = temp_path("offline");
+        let _ = fs::remove_file(&path);
+
+        let mut seed: PersistentStorage<String> = PersistentStorage::load(&path).unwrap();
+        seed.add(Document::new(1, "cached".to_string(), 0));
+
+        let (storage, new_ids, gone_ids) =
+            PersistentStorage::<String>::open(&path, false, || panic!("remote should not be called"))
+                .unwrap();
+
+        assert_eq!(storage.get(1).unwrap().content(), "cached");
+        assert!(new_ids.is_empty());
+        assert!(gone_ids.is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn online_open_reconciles_against_remote() {
+        let path = temp_path("online");
+        let _ = fs::remove_file(&path);
+
+        let mut seed: PersistentStorage<String> = PersistentStorage::load(&path).unwrap();
+        seed.add(Document::new(1, "stays".to_string(), 0));
+        seed.add(Document::new(2, "goes".to_string(), 0));
+
+        let (storage, mut new_ids, mut gone_ids) = PersistentStorage::<String>::open(&path, true, || {
+            Ok(vec![
+                Document::new(1, "stays".to_string(), 0),
+                Document::new(3, "arrives".to_string(), 1),
+            ])
+        })
+        .unwrap();
+
+        new_ids.sort_unstable();
+        gone_ids.sort_unstable();
+        assert_eq!(new_ids, vec![3]);
+        assert_eq!(gone_ids, vec![2]);
+        assert!(storage.get(1).is_some());
+        assert!(storage.get(2).is_none());
+        assert!(storage.get(3).is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+}